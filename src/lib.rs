@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::str::FromStr;
 
 use pyo3::create_exception;
 use pyo3::exceptions::PyException;
@@ -21,45 +22,791 @@ use pyo3::types::IntoPyDict;
 use pyo3::wrap_pyfunction;
 
 create_exception!(readwrite_ufo_glif, GlifReadError, PyException);
+create_exception!(readwrite_ufo_glif, GlifWriteError, PyException);
 
-#[pyfunction]
-#[text_signature = "(layer_path, /)"]
-fn read_layer(layer_path: &str) -> PyResult<HashMap<String, PyObject>> {
-    let layer = norad::Layer::load(&layer_path, "".into()).map_err(|e| {
-        GlifReadError::new_err(format!("Failed to read layer at '{}': {}", layer_path, e))
-    })?;
+// Specific causes of a `GlifReadError`, mirroring `norad::error::ErrorKind`, so
+// callers can narrow their `except` clauses to the failure they care about
+// instead of pattern-matching the message string.
+create_exception!(readwrite_ufo_glif, UnsupportedGlifVersionError, GlifReadError);
+create_exception!(readwrite_ufo_glif, GlifXmlError, GlifReadError);
+create_exception!(readwrite_ufo_glif, BadPointTypeError, GlifReadError);
+create_exception!(readwrite_ufo_glif, DuplicateIdentifierError, GlifReadError);
+create_exception!(readwrite_ufo_glif, BadColorError, GlifReadError);
+
+/// Turn a `norad::Error` from loading a `.glif` file into the most specific
+/// exception subclass available, attaching `.path`, `.glyph_name`, and, for
+/// spec violations (`norad::Error::Glif`), the byte `.position` in the file.
+fn glif_load_error(py: Python, err: &norad::Error, path: &str, glyph_name: Option<&str>) -> PyErr {
+    use norad::error::ErrorKind::*;
+
+    let message = err.to_string();
+    let pyerr = match err {
+        norad::Error::ParseError(_) => GlifXmlError::new_err(message),
+        norad::Error::Glif(glif_err) => match glif_err.kind {
+            UnsupportedGlifVersion => UnsupportedGlifVersionError::new_err(message),
+            UnknownPointType | BadPoint => BadPointTypeError::new_err(message),
+            DuplicateIdentifier => DuplicateIdentifierError::new_err(message),
+            BadColor => BadColorError::new_err(message),
+            _ => GlifReadError::new_err(message),
+        },
+        _ => GlifReadError::new_err(message),
+    };
+
+    let instance = pyerr.instance(py);
+    let _ = instance.setattr("path", path);
+    let _ = instance.setattr("glyph_name", glyph_name);
+    if let norad::Error::Glif(glif_err) = err {
+        let _ = instance.setattr("position", glif_err.position);
+    }
+
+    pyerr
+}
+
+#[pyfunction(num_threads = "None")]
+#[pyo3(text_signature = "(layer_path, /, num_threads=None)")]
+fn read_layer(
+    layer_path: &str,
+    num_threads: Option<usize>,
+    py: Python,
+) -> PyResult<HashMap<String, PyObject>> {
+    // Parse all glif files off the GIL, in parallel, then only take the GIL
+    // back for the comparatively cheap work of building the result dicts.
+    let glyphs = py.allow_threads(|| parse_layer_parallel(layer_path, num_threads))?;
 
     let mut dicts: HashMap<String, PyObject> = HashMap::new();
-    let gil = Python::acquire_gil();
-    let py = gil.python();
-    for glyph in layer.iter().map(|g| g.as_ref()) {
-        let glyph_dict = convert_glyph(glyph, py)?;
-        dicts.insert(glyph.name.to_string(), glyph_dict);
+    for (name, glyph) in &glyphs {
+        dicts.insert(name.clone(), convert_glyph(glyph, py)?);
     }
 
     Ok(dicts)
 }
 
+/// Parse every glyph in a layer directory off the GIL, across up to
+/// `num_threads` rayon worker threads (the global rayon pool if `None`).
+fn parse_layer_parallel(
+    layer_path: &str,
+    num_threads: Option<usize>,
+) -> PyResult<Vec<(String, norad::Glyph)>> {
+    use rayon::prelude::*;
+
+    let layer_path = std::path::Path::new(layer_path);
+    let contents_path = layer_path.join("contents.plist");
+    let contents: HashMap<String, std::path::PathBuf> =
+        plist::from_file(&contents_path).map_err(|e| {
+            GlifReadError::new_err(format!(
+                "Failed to read contents.plist in layer at '{}': {}",
+                layer_path.display(),
+                e
+            ))
+        })?;
+
+    let parse_all = || {
+        contents
+            .into_par_iter()
+            .map(|(name, file_name)| {
+                let glif_path = layer_path.join(&file_name);
+                norad::Glyph::load(&glif_path)
+                    .map(|glyph| (name, glyph))
+                    .map_err(|e| (glif_path, e))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    let result = match num_threads {
+        Some(num_threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .map_err(|e| GlifReadError::new_err(format!("Failed to set up thread pool: {}", e)))?
+            .install(parse_all),
+        None => parse_all(),
+    };
+
+    result.map_err(|(path, e)| {
+        Python::with_gil(|py| glif_load_error(py, &e, &path.to_string_lossy(), None))
+    })
+}
+
 #[pyfunction]
-#[text_signature = "(glif_path, /)"]
+#[pyo3(text_signature = "(glif_path, /)")]
 fn read_glyph(glif_path: &str) -> PyResult<PyObject> {
-    let glyph = norad::Glyph::load(&glif_path).map_err(|e| {
-        GlifReadError::new_err(format!("Failed to read glif file at '{}': {}", glif_path, e))
-    })?;
-
     let gil = Python::acquire_gil();
     let py = gil.python();
+    let glyph = norad::Glyph::load(glif_path)
+        .map_err(|e| glif_load_error(py, &e, glif_path, None))?;
+
     let glyph_dict = convert_glyph(&glyph, py)?;
 
     Ok(glyph_dict)
 }
 
+#[pyfunction]
+#[pyo3(text_signature = "(layer_path, pen_factory, /)")]
+fn read_layer_to_pen(layer_path: &str, pen_factory: PyObject, py: Python) -> PyResult<()> {
+    let layer = norad::Layer::load(layer_path, "".into())
+        .map_err(|e| glif_load_error(py, &e, layer_path, None))?;
+
+    for glyph in layer.iter().map(|g| g.as_ref()) {
+        let pen = pen_factory.call1(py, (glyph.name.to_string(),))?;
+        draw_glyph_to_pen(glyph, pen.as_ref(py), py)?;
+    }
+
+    Ok(())
+}
+
+#[pyfunction]
+#[pyo3(text_signature = "(glif_path, pen, /)")]
+fn read_glyph_to_pen(glif_path: &str, pen: &PyAny, py: Python) -> PyResult<()> {
+    let glyph = norad::Glyph::load(glif_path)
+        .map_err(|e| glif_load_error(py, &e, glif_path, None))?;
+
+    draw_glyph_to_pen(&glyph, pen, py)
+}
+
+/// Drive a fontTools-style `PointPen` with the contents of `glyph`, instead of
+/// materializing the nested dicts that `convert_glyph` builds. This avoids the
+/// intermediate allocation, which matters when streaming thousands of glyphs
+/// straight into ufoLib/defcon.
+fn draw_glyph_to_pen(glyph: &norad::Glyph, pen: &PyAny, py: Python) -> PyResult<()> {
+    for contour in &glyph.contours {
+        let identifier = contour.identifier().as_ref().map(|id| id.as_str());
+        pen.call_method1("beginPath", (identifier.to_object(py),))?;
+        for point in &contour.points {
+            let segment_type = match point.typ {
+                norad::PointType::Move => Some("move"),
+                norad::PointType::Line => Some("line"),
+                norad::PointType::OffCurve => None,
+                norad::PointType::Curve => Some("curve"),
+                norad::PointType::QCurve => Some("qcurve"),
+            };
+            let identifier = point.identifier().as_ref().map(|id| id.as_str());
+            let kwargs = [
+                ("segmentType", segment_type.to_object(py)),
+                ("smooth", point.smooth.to_object(py)),
+                ("name", point.name.to_object(py)),
+                ("identifier", identifier.to_object(py)),
+            ]
+            .into_py_dict(py);
+            pen.call_method("addPoint", ((point.x, point.y),), Some(kwargs))?;
+        }
+        pen.call_method0("endPath")?;
+    }
+
+    for component in &glyph.components {
+        let transform = (
+            component.transform.x_scale,
+            component.transform.xy_scale,
+            component.transform.yx_scale,
+            component.transform.y_scale,
+            component.transform.x_offset,
+            component.transform.y_offset,
+        );
+        let identifier = component.identifier().as_ref().map(|id| id.as_str());
+        let kwargs = [("identifier", identifier.to_object(py))].into_py_dict(py);
+        pen.call_method(
+            "addComponent",
+            (component.base.to_string(), transform),
+            Some(kwargs),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[pyfunction]
+#[pyo3(text_signature = "(ufo_path, /)")]
+fn read_font(ufo_path: &str, py: Python) -> PyResult<PyObject> {
+    let request = norad::DataRequest::default();
+    let font = norad::Font::load_requested_data(ufo_path, request).map_err(|e| {
+        GlifReadError::new_err(format!("Failed to read UFO at '{}': {}", ufo_path, e))
+    })?;
+
+    let mut layers: HashMap<String, PyObject> = HashMap::new();
+    for layer in font.iter_layers() {
+        let mut glyphs: HashMap<String, PyObject> = HashMap::new();
+        for glyph in layer.iter().map(|g| g.as_ref()) {
+            glyphs.insert(glyph.name.to_string(), convert_glyph(glyph, py)?);
+        }
+        layers.insert(layer.name().to_string(), glyphs.into_py_dict(py).to_object(py));
+    }
+
+    let empty_dict = || HashMap::<&str, PyObject>::new().into_py_dict(py).to_object(py);
+
+    let mut font_dict: HashMap<&str, PyObject> = HashMap::new();
+    font_dict.insert("layers", layers.into_py_dict(py).to_object(py));
+    font_dict.insert(
+        "fontinfo",
+        font.font_info
+            .as_ref()
+            .map_or_else(empty_dict, |info| convert_fontinfo(info, py)),
+    );
+    font_dict.insert(
+        "groups",
+        font.groups
+            .as_ref()
+            .map_or_else(empty_dict, |groups| convert_groups(groups, py)),
+    );
+    font_dict.insert(
+        "kerning",
+        font.kerning
+            .as_ref()
+            .map_or_else(empty_dict, |kerning| convert_kerning(kerning, py)),
+    );
+
+    if let Some(features) = &font.features {
+        font_dict.insert("features", features.to_object(py));
+    }
+
+    let mut lib = HashMap::<&str, PyObject>::new();
+    for (key, value) in font.lib.iter() {
+        let py_value = convert_lib_key_value(key, value, py).map_err(|e| {
+            GlifReadError::new_err(format!("Failed to convert font lib data: {}", e))
+        })?;
+        lib.insert(key, py_value);
+    }
+    font_dict.insert("lib", lib.into_py_dict(py).to_object(py));
+
+    Ok(font_dict.to_object(py))
+}
+
+#[pyfunction(quote_char = "'\"'", indent = "\"  \"")]
+#[pyo3(text_signature = "(glif_path, glyph_dict, /, quote_char='\"', indent='  ')")]
+fn write_glyph(
+    glif_path: &str,
+    glyph_dict: HashMap<String, PyObject>,
+    quote_char: char,
+    indent: &str,
+    py: Python,
+) -> PyResult<()> {
+    let glyph = build_glyph(&glyph_dict, py).map_err(|e| {
+        GlifWriteError::new_err(format!("Failed to build glyph for '{}': {}", glif_path, e))
+    })?;
+
+    let options = write_options(quote_char, indent)?;
+    let xml = glyph.encode_xml_with_options(&options).map_err(|e| {
+        GlifWriteError::new_err(format!(
+            "Failed to write glif file at '{}': {}",
+            glif_path, e
+        ))
+    })?;
+
+    std::fs::write(glif_path, xml)
+        .map_err(|e| GlifWriteError::new_err(format!("Failed to write '{}': {}", glif_path, e)))?;
+
+    Ok(())
+}
+
+#[pyfunction(quote_char = "'\"'", indent = "\"  \"")]
+#[pyo3(text_signature = "(layer_path, glyphs_dict, /, quote_char='\"', indent='  ')")]
+fn write_layer(
+    layer_path: &str,
+    glyphs_dict: HashMap<String, HashMap<String, PyObject>>,
+    quote_char: char,
+    indent: &str,
+    py: Python,
+) -> PyResult<()> {
+    let options = write_options(quote_char, indent)?;
+    let layer_path = std::path::Path::new(layer_path);
+    std::fs::create_dir_all(layer_path).map_err(|e| {
+        GlifWriteError::new_err(format!(
+            "Failed to create layer directory at '{}': {}",
+            layer_path.display(),
+            e
+        ))
+    })?;
+
+    // Sort so that file name assignment (and thus which glyph wins the plain
+    // name in a case-insensitive clash) is deterministic across runs, instead
+    // of depending on `glyphs_dict`'s randomized HashMap iteration order.
+    let mut glyph_names: Vec<&String> = glyphs_dict.keys().collect();
+    glyph_names.sort();
+
+    let mut file_names = std::collections::HashSet::new();
+    let mut contents = plist::Dictionary::new();
+    for glyph_name in glyph_names {
+        let glyph_dict = &glyphs_dict[glyph_name];
+        let glyph = build_glyph(glyph_dict, py).map_err(|e| {
+            GlifWriteError::new_err(format!(
+                "Failed to build glyph '{}' for layer at '{}': {}",
+                glyph_name,
+                layer_path.display(),
+                e
+            ))
+        })?;
+
+        let file_name = user_name_to_file_name(glyph_name.clone(), file_names.clone(), "", ".glif");
+        file_names.insert(file_name.clone());
+        contents.insert(
+            glyph_name.clone(),
+            plist::Value::String(file_name.clone()),
+        );
+
+        let xml = glyph.encode_xml_with_options(&options).map_err(|e| {
+            GlifWriteError::new_err(format!(
+                "Failed to write glyph '{}' in layer at '{}': {}",
+                glyph_name,
+                layer_path.display(),
+                e
+            ))
+        })?;
+
+        std::fs::write(layer_path.join(&file_name), xml).map_err(|e| {
+            GlifWriteError::new_err(format!(
+                "Failed to write glyph '{}' in layer at '{}': {}",
+                glyph_name,
+                layer_path.display(),
+                e
+            ))
+        })?;
+    }
+
+    plist::Value::Dictionary(contents)
+        .to_file_xml(layer_path.join("contents.plist"))
+        .map_err(|e| {
+            GlifWriteError::new_err(format!(
+                "Failed to write contents.plist in layer at '{}': {}",
+                layer_path.display(),
+                e
+            ))
+        })?;
+
+    Ok(())
+}
+
 #[pymodule]
 fn readwrite_ufo_glif(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(read_layer, m)?)?;
     m.add_function(wrap_pyfunction!(read_glyph, m)?)?;
+    m.add_function(wrap_pyfunction!(read_layer_to_pen, m)?)?;
+    m.add_function(wrap_pyfunction!(read_glyph_to_pen, m)?)?;
+    m.add_function(wrap_pyfunction!(read_font, m)?)?;
+    m.add_function(wrap_pyfunction!(write_layer, m)?)?;
+    m.add_function(wrap_pyfunction!(write_glyph, m)?)?;
+    m.add_function(wrap_pyfunction!(user_name_to_file_name, m)?)?;
 
     m.add("GlifReadError", py.get_type::<GlifReadError>())?;
+    m.add("GlifWriteError", py.get_type::<GlifWriteError>())?;
+    m.add(
+        "UnsupportedGlifVersionError",
+        py.get_type::<UnsupportedGlifVersionError>(),
+    )?;
+    m.add("GlifXmlError", py.get_type::<GlifXmlError>())?;
+    m.add("BadPointTypeError", py.get_type::<BadPointTypeError>())?;
+    m.add(
+        "DuplicateIdentifierError",
+        py.get_type::<DuplicateIdentifierError>(),
+    )?;
+    m.add("BadColorError", py.get_type::<BadColorError>())?;
+
+    Ok(())
+}
+
+fn write_options(quote_char: char, indent: &str) -> PyResult<norad::WriteOptions> {
+    let quote_char = match quote_char {
+        '\'' => norad::QuoteChar::Single,
+        '"' => norad::QuoteChar::Double,
+        c => {
+            return Err(GlifWriteError::new_err(format!(
+                "quote_char must be ' or \", got '{}'",
+                c
+            )))
+        }
+    };
+
+    Ok(norad::WriteOptions::default()
+        .quote_char(quote_char)
+        .whitespace(indent.to_string()))
+}
+
+/// Build a `norad::Glyph` from a dict in the shape `convert_glyph` produces, the
+/// inverse direction, plus a required `"name"` entry that `convert_glyph` does
+/// not emit. `norad` only ever writes glif format 2, so an integer `"format"`
+/// entry other than 2 is rejected outright; the points and other contents are
+/// not checked against the glif format.
+fn build_glyph(glyph_dict: &HashMap<String, PyObject>, py: Python) -> PyResult<norad::Glyph> {
+    if let Some(format) = glyph_dict.get("format") {
+        let format: i32 = format.extract(py).unwrap_or(2);
+        if format != 2 {
+            return Err(GlifWriteError::new_err(format!(
+                "only glif format 2 can be written, but the dict asked for format {}",
+                format
+            )));
+        }
+    }
+
+    let name: String = glyph_dict
+        .get("name")
+        .ok_or_else(|| GlifWriteError::new_err("glyph dict is missing a 'name' entry"))?
+        .extract(py)?;
+    let mut glyph = norad::Glyph::new_named(name);
+
+    if let Some(unicodes) = glyph_dict.get("unicodes") {
+        let codepoints: Vec<u32> = unicodes.extract(py)?;
+        glyph.codepoints = codepoints
+            .into_iter()
+            .filter_map(char::from_u32)
+            .collect();
+    }
+    if let Some(width) = glyph_dict.get("width") {
+        glyph.width = width.extract(py)?;
+    }
+    if let Some(height) = glyph_dict.get("height") {
+        glyph.height = height.extract(py)?;
+    }
+    if let Some(note) = glyph_dict.get("note") {
+        glyph.note = note.extract(py)?;
+    }
+
+    if let Some(image) = glyph_dict.get("image") {
+        let image: HashMap<String, PyObject> = image.extract(py)?;
+        glyph.image = Some(build_image(&image, py)?);
+    }
+
+    if let Some(anchors) = glyph_dict.get("anchors") {
+        let anchors: Vec<HashMap<String, PyObject>> = anchors.extract(py)?;
+        glyph.anchors = anchors
+            .iter()
+            .map(|a| build_anchor(a, py))
+            .collect::<PyResult<_>>()?;
+    }
+
+    if let Some(guidelines) = glyph_dict.get("guidelines") {
+        let guidelines: Vec<HashMap<String, PyObject>> = guidelines.extract(py)?;
+        glyph.guidelines = guidelines
+            .iter()
+            .map(|g| build_guideline(g, py))
+            .collect::<PyResult<_>>()?;
+    }
+
+    if let Some(contours) = glyph_dict.get("contours") {
+        let contours: Vec<HashMap<String, PyObject>> = contours.extract(py)?;
+        glyph.contours = contours
+            .iter()
+            .map(|c| build_contour(c, py))
+            .collect::<PyResult<_>>()?;
+    }
+
+    if let Some(components) = glyph_dict.get("components") {
+        let components: Vec<HashMap<String, PyObject>> = components.extract(py)?;
+        glyph.components = components
+            .iter()
+            .map(|c| build_component(c, py))
+            .collect::<PyResult<_>>()?;
+    }
+
+    if let Some(lib) = glyph_dict.get("lib") {
+        let lib: HashMap<String, PyObject> = lib.extract(py)?;
+        let object_libs = lib
+            .get("public.objectLibs")
+            .map(|v| build_lib_value("public.objectLibs", v, py))
+            .transpose()?;
+        glyph.lib = build_lib(&lib, py)?;
+        load_object_libs(&mut glyph, object_libs)?;
+    }
+
+    Ok(glyph)
+}
+
+fn build_image(image: &HashMap<String, PyObject>, py: Python) -> PyResult<norad::Image> {
+    let file_name: String = image
+        .get("fileName")
+        .ok_or_else(|| GlifWriteError::new_err("image is missing 'fileName'"))?
+        .extract(py)?;
+    let transform = image
+        .get("transformation")
+        .map(|t| build_transform(t, py))
+        .transpose()?
+        .unwrap_or_default();
+    let color = build_color(image.get("color"), py)?;
+
+    Ok(norad::Image {
+        file_name: file_name.into(),
+        transform,
+        color,
+    })
+}
+
+fn build_transform(transform: &PyObject, py: Python) -> PyResult<norad::AffineTransform> {
+    let (x_scale, xy_scale, yx_scale, y_scale, x_offset, y_offset): (
+        f32,
+        f32,
+        f32,
+        f32,
+        f32,
+        f32,
+    ) = transform.extract(py)?;
+    Ok(norad::AffineTransform {
+        x_scale,
+        xy_scale,
+        yx_scale,
+        y_scale,
+        x_offset,
+        y_offset,
+    })
+}
+
+fn build_color(color: Option<&PyObject>, py: Python) -> PyResult<Option<norad::Color>> {
+    match color {
+        None => Ok(None),
+        Some(color) => {
+            let color: Option<String> = color.extract(py)?;
+            color
+                .map(|c| {
+                    norad::Color::from_str(&c).map_err(|e| {
+                        GlifWriteError::new_err(format!("invalid color '{}': {}", c, e))
+                    })
+                })
+                .transpose()
+        }
+    }
+}
+
+fn build_identifier(identifier: Option<&PyObject>, py: Python) -> PyResult<Option<norad::Identifier>> {
+    match identifier {
+        None => Ok(None),
+        Some(identifier) => {
+            let identifier: Option<String> = identifier.extract(py)?;
+            identifier
+                .map(|id| {
+                    norad::Identifier::new(id.clone())
+                        .map_err(|e| GlifWriteError::new_err(format!("invalid identifier '{}': {}", id, e)))
+                })
+                .transpose()
+        }
+    }
+}
+
+fn build_anchor(anchor: &HashMap<String, PyObject>, py: Python) -> PyResult<norad::Anchor> {
+    let x: f32 = anchor
+        .get("x")
+        .ok_or_else(|| GlifWriteError::new_err("anchor is missing 'x'"))?
+        .extract(py)?;
+    let y: f32 = anchor
+        .get("y")
+        .ok_or_else(|| GlifWriteError::new_err("anchor is missing 'y'"))?
+        .extract(py)?;
+    let name: Option<String> = anchor
+        .get("name")
+        .map(|v| v.extract::<Option<String>>(py))
+        .transpose()?
+        .flatten();
+    let color = build_color(anchor.get("color"), py)?;
+    let identifier = build_identifier(anchor.get("identifier"), py)?;
+
+    Ok(norad::Anchor::new(x, y, name, color, identifier, None))
+}
+
+fn build_guideline(guideline: &HashMap<String, PyObject>, py: Python) -> PyResult<norad::Guideline> {
+    let get = |key: &str| guideline.get(key).map(|v| v.extract::<Option<f32>>(py));
+    let x: Option<f32> = get("x").transpose()?.flatten();
+    let y: Option<f32> = get("y").transpose()?.flatten();
+    let angle: Option<f32> = get("angle").transpose()?.flatten();
+
+    let line = match (x, y, angle) {
+        (Some(x), None, None) => norad::Line::Vertical(x),
+        (None, Some(y), None) => norad::Line::Horizontal(y),
+        (Some(x), Some(y), Some(degrees)) => norad::Line::Angle { x, y, degrees },
+        _ => {
+            return Err(GlifWriteError::new_err(
+                "guideline must set either 'x', 'y', or all of 'x', 'y' and 'angle'",
+            ))
+        }
+    };
+
+    let name: Option<String> = guideline
+        .get("name")
+        .map(|v| v.extract::<Option<String>>(py))
+        .transpose()?
+        .flatten();
+    let color = build_color(guideline.get("color"), py)?;
+    let identifier = build_identifier(guideline.get("identifier"), py)?;
+
+    Ok(norad::Guideline::new(line, name, color, identifier, None))
+}
+
+fn build_contour(contour: &HashMap<String, PyObject>, py: Python) -> PyResult<norad::Contour> {
+    let points: Vec<HashMap<String, PyObject>> = contour
+        .get("points")
+        .ok_or_else(|| GlifWriteError::new_err("contour is missing 'points'"))?
+        .extract(py)?;
+    let points = points
+        .iter()
+        .map(|p| build_point(p, py))
+        .collect::<PyResult<_>>()?;
+    let identifier = build_identifier(contour.get("identifier"), py)?;
+
+    Ok(norad::Contour::new(points, identifier, None))
+}
+
+fn build_point(point: &HashMap<String, PyObject>, py: Python) -> PyResult<norad::ContourPoint> {
+    let x: f32 = point
+        .get("x")
+        .ok_or_else(|| GlifWriteError::new_err("point is missing 'x'"))?
+        .extract(py)?;
+    let y: f32 = point
+        .get("y")
+        .ok_or_else(|| GlifWriteError::new_err("point is missing 'y'"))?
+        .extract(py)?;
+    let typ: Option<String> = point
+        .get("type")
+        .map(|v| v.extract::<Option<String>>(py))
+        .transpose()?
+        .flatten();
+    let typ = match typ.as_deref() {
+        None => norad::PointType::OffCurve,
+        Some("move") => norad::PointType::Move,
+        Some("line") => norad::PointType::Line,
+        Some("curve") => norad::PointType::Curve,
+        Some("qcurve") => norad::PointType::QCurve,
+        Some(other) => {
+            return Err(GlifWriteError::new_err(format!(
+                "unknown point type '{}'",
+                other
+            )))
+        }
+    };
+    let smooth: bool = point
+        .get("smooth")
+        .map(|v| v.extract(py))
+        .transpose()?
+        .unwrap_or(false);
+    let name: Option<String> = point
+        .get("name")
+        .map(|v| v.extract::<Option<String>>(py))
+        .transpose()?
+        .flatten();
+    let identifier = build_identifier(point.get("identifier"), py)?;
+
+    Ok(norad::ContourPoint::new(
+        x, y, typ, smooth, name, identifier, None,
+    ))
+}
+
+fn build_component(component: &HashMap<String, PyObject>, py: Python) -> PyResult<norad::Component> {
+    let base: String = component
+        .get("baseGlyph")
+        .ok_or_else(|| GlifWriteError::new_err("component is missing 'baseGlyph'"))?
+        .extract(py)?;
+    let transform = component
+        .get("transformation")
+        .map(|t| build_transform(t, py))
+        .transpose()?
+        .unwrap_or_default();
+    let identifier = build_identifier(component.get("identifier"), py)?;
+
+    Ok(norad::Component::new(base.into(), transform, identifier, None))
+}
+
+/// Inverse of `convert_lib_key_value`: turn a Python value back into a `plist::Value`.
+fn build_lib_value(key: &str, value: &PyObject, py: Python) -> PyResult<plist::Value> {
+    if let Ok(s) = value.extract::<String>(py) {
+        return Ok(plist::Value::String(s));
+    }
+    if let Ok(b) = value.extract::<bool>(py) {
+        return Ok(plist::Value::Boolean(b));
+    }
+    if let Ok(i) = value.extract::<i64>(py) {
+        return Ok(plist::Value::Integer(i.into()));
+    }
+    if let Ok(i) = value.extract::<u64>(py) {
+        return Ok(plist::Value::Integer(i.into()));
+    }
+    if let Ok(r) = value.extract::<f64>(py) {
+        return Ok(plist::Value::Real(r));
+    }
+    // Check for `bytes`/`bytearray` specifically: a generic `Vec<u8>`
+    // extraction would also match a plain `list` of small ints, silently
+    // turning e.g. `[1, 2, 3]` into plist `Data` instead of an Array.
+    if let Ok(data) = value.as_ref(py).downcast::<pyo3::types::PyBytes>() {
+        return Ok(plist::Value::Data(data.as_bytes().to_vec()));
+    }
+    if let Ok(date) = value.as_ref(py).downcast::<pyo3::types::PyDateTime>() {
+        let timestamp: f64 = date.call_method0("timestamp")?.extract()?;
+        if timestamp >= 0.0 {
+            let time = std::time::UNIX_EPOCH + std::time::Duration::from_secs_f64(timestamp);
+            return Ok(plist::Value::Date(time.into()));
+        }
+    }
+    if let Ok(a) = value.extract::<Vec<PyObject>>(py) {
+        let values = a
+            .iter()
+            .map(|v| build_lib_value(key, v, py))
+            .collect::<PyResult<_>>()?;
+        return Ok(plist::Value::Array(values));
+    }
+    if let Ok(d) = value.extract::<HashMap<String, PyObject>>(py) {
+        return Ok(plist::Value::Dictionary(build_lib(&d, py)?));
+    }
+
+    Err(GlifWriteError::new_err(format!(
+        "lib value for key '{}' cannot be converted to a plist value",
+        key
+    )))
+}
+
+fn build_lib(lib: &HashMap<String, PyObject>, py: Python) -> PyResult<norad::Plist> {
+    let mut plist = norad::Plist::default();
+    for (key, value) in lib.iter() {
+        if key == "public.objectLibs" {
+            continue;
+        }
+        plist.insert(key.clone(), build_lib_value(key, value, py)?);
+    }
+    Ok(plist)
+}
+
+/// Unpack `public.objectLibs` (captured from the Python lib dict before
+/// `build_lib` strips it out, since `build_lib` never stores it on
+/// `glyph.lib` in the first place) back onto the per-object identifiers that
+/// `dump_object_libs` flattened them from.
+fn load_object_libs(glyph: &mut norad::Glyph, object_libs: Option<plist::Value>) -> PyResult<()> {
+    let object_libs = match object_libs {
+        Some(plist::Value::Dictionary(d)) => d,
+        Some(_) => {
+            return Err(GlifWriteError::new_err(
+                "public.objectLibs must be a dictionary",
+            ))
+        }
+        None => return Ok(()),
+    };
+
+    let set_lib = |id: Option<&norad::Identifier>, set: &mut dyn FnMut(norad::Plist)| {
+        if let Some(id) = id {
+            if let Some(plist::Value::Dictionary(lib)) = object_libs.get(id.as_str()) {
+                set(lib.clone());
+            }
+        }
+    };
+
+    for anchor in &mut glyph.anchors {
+        let id = anchor.identifier().cloned();
+        set_lib(id.as_ref(), &mut |lib| {
+            anchor.replace_lib(lib);
+        });
+    }
+    for guideline in &mut glyph.guidelines {
+        let id = guideline.identifier().cloned();
+        set_lib(id.as_ref(), &mut |lib| {
+            guideline.replace_lib(lib);
+        });
+    }
+    for component in &mut glyph.components {
+        let id = component.identifier().cloned();
+        set_lib(id.as_ref(), &mut |lib| {
+            component.replace_lib(lib);
+        });
+    }
+    for contour in &mut glyph.contours {
+        let id = contour.identifier().cloned();
+        set_lib(id.as_ref(), &mut |lib| {
+            contour.replace_lib(lib);
+        });
+        for point in &mut contour.points {
+            let id = point.identifier().cloned();
+            set_lib(id.as_ref(), &mut |lib| {
+                point.replace_lib(lib);
+            });
+        }
+    }
 
     Ok(())
 }
@@ -135,7 +882,7 @@ fn convert_glyph(glyph: &norad::Glyph, py: Python) -> PyResult<PyObject> {
         })?;
         glyph_lib.insert(key, py_value);
     }
-    let object_libs_plist = dump_object_libs(&glyph);
+    let object_libs_plist = dump_object_libs(glyph);
     if !object_libs_plist.is_empty() {
         let object_libs = convert_object_lib(&object_libs_plist, py).map_err(|e| {
             GlifReadError::new_err(format!(
@@ -312,6 +1059,250 @@ fn convert_component(component: &norad::Component, py: Python) -> PyObject {
     .to_object(py)
 }
 
+fn convert_groups(groups: &norad::Groups, py: Python) -> PyObject {
+    let dict: HashMap<&str, PyObject> = groups
+        .iter()
+        .map(|(name, members)| {
+            let members: Vec<&str> = members.iter().map(|m| m.as_ref()).collect();
+            (name.as_str(), members.to_object(py))
+        })
+        .collect();
+    dict.into_py_dict(py).to_object(py)
+}
+
+fn convert_kerning(kerning: &norad::Kerning, py: Python) -> PyObject {
+    let dict: HashMap<&str, PyObject> = kerning
+        .iter()
+        .map(|(first, seconds)| {
+            let seconds: HashMap<&str, PyObject> = seconds
+                .iter()
+                .map(|(second, value)| (second.as_str(), value.to_object(py)))
+                .collect();
+            (first.as_str(), seconds.into_py_dict(py).to_object(py))
+        })
+        .collect();
+    dict.into_py_dict(py).to_object(py)
+}
+
+/// Convert a `norad::FontInfo` to the dict shape of fontinfo.plist, following
+/// the grouping of the UFO3 spec. Only the keys set below are converted:
+/// `guidelines`, `openTypeGaspRangeRecords`, `openTypeNameDescription`,
+/// `openTypeNameRecords`, `openTypeOS2Selection` and the `woff*` keys are not
+/// included. Absent fields are left out rather than written as `None`, as
+/// `convert_glyph` does for its own top-level entries.
+fn convert_fontinfo(info: &norad::FontInfo, py: Python) -> PyObject {
+    let mut dict = HashMap::<&str, PyObject>::new();
+
+    macro_rules! set {
+        ($key:literal, $field:ident) => {
+            if let Some(value) = &info.$field {
+                dict.insert($key, value.to_object(py));
+            }
+        };
+    }
+
+    // Generic Identification Information.
+    set!("familyName", family_name);
+    set!("styleName", style_name);
+    set!("styleMapFamilyName", style_map_family_name);
+    if let Some(style) = &info.style_map_style_name {
+        let style = match style {
+            norad::fontinfo::StyleMapStyle::Regular => "regular",
+            norad::fontinfo::StyleMapStyle::Italic => "italic",
+            norad::fontinfo::StyleMapStyle::Bold => "bold",
+            norad::fontinfo::StyleMapStyle::BoldItalic => "bold italic",
+        };
+        dict.insert("styleMapStyleName", style.to_object(py));
+    }
+    set!("versionMajor", version_major);
+    set!("versionMinor", version_minor);
+    set!("year", year);
+
+    // Generic Legal Information.
+    set!("copyright", copyright);
+    set!("trademark", trademark);
+
+    // Generic Dimension Information.
+    set!("unitsPerEm", units_per_em);
+    set!("descender", descender);
+    set!("xHeight", x_height);
+    set!("capHeight", cap_height);
+    set!("ascender", ascender);
+    set!("italicAngle", italic_angle);
+
+    // Generic Miscellaneous Information.
+    set!("note", note);
+
+    // OpenType head Table Fields.
+    set!("openTypeHeadCreated", open_type_head_created);
+    set!("openTypeHeadLowestRecPPEM", open_type_head_lowest_rec_ppem);
+    set!("openTypeHeadFlags", open_type_head_flags);
+
+    // OpenType hhea Table Fields.
+    set!("openTypeHheaAscender", open_type_hhea_ascender);
+    set!("openTypeHheaDescender", open_type_hhea_descender);
+    set!("openTypeHheaLineGap", open_type_hhea_line_gap);
+    set!("openTypeHheaCaretSlopeRise", open_type_hhea_caret_slope_rise);
+    set!("openTypeHheaCaretSlopeRun", open_type_hhea_caret_slope_run);
+    set!("openTypeHheaCaretOffset", open_type_hhea_caret_offset);
+
+    // OpenType Name Table Fields.
+    set!("openTypeNameDesigner", open_type_name_designer);
+    set!("openTypeNameDesignerURL", open_type_name_designer_url);
+    set!("openTypeNameManufacturer", open_type_name_manufacturer);
+    set!("openTypeNameManufacturerURL", open_type_name_manufacturer_url);
+    set!("openTypeNameLicense", open_type_name_license);
+    set!("openTypeNameLicenseURL", open_type_name_license_url);
+    set!("openTypeNameVersion", open_type_name_version);
+    set!("openTypeNameUniqueID", open_type_name_unique_id);
+    set!(
+        "openTypeNamePreferredFamilyName",
+        open_type_name_preferred_family_name
+    );
+    set!(
+        "openTypeNamePreferredSubfamilyName",
+        open_type_name_preferred_subfamily_name
+    );
+    set!(
+        "openTypeNameCompatibleFullName",
+        open_type_name_compatible_full_name
+    );
+    set!("openTypeNameSampleText", open_type_name_sample_text);
+    set!("openTypeNameWWSFamilyName", open_type_name_wws_family_name);
+    set!(
+        "openTypeNameWWSSubfamilyName",
+        open_type_name_wws_subfamily_name
+    );
+
+    // OpenType OS/2 Table Fields.
+    if let Some(width_class) = &info.open_type_os2_width_class {
+        dict.insert("openTypeOS2WidthClass", (*width_class as u8).to_object(py));
+    }
+    set!("openTypeOS2WeightClass", open_type_os2_weight_class);
+    set!("openTypeOS2VendorID", open_type_os2_vendor_id);
+    if let Some(panose) = &info.open_type_os2_panose {
+        let panose = (
+            panose.family_type,
+            panose.serif_style,
+            panose.weight,
+            panose.proportion,
+            panose.contrast,
+            panose.stroke_variation,
+            panose.arm_style,
+            panose.letterform,
+            panose.midline,
+            panose.x_height,
+        );
+        dict.insert("openTypeOS2Panose", panose.to_object(py));
+    }
+    if let Some(family_class) = &info.open_type_os2_family_class {
+        let family_class = (family_class.class_id, family_class.subclass_id);
+        dict.insert("openTypeOS2FamilyClass", family_class.to_object(py));
+    }
+    set!("openTypeOS2UnicodeRanges", open_type_os2_unicode_ranges);
+    set!("openTypeOS2CodePageRanges", open_type_os2_code_page_ranges);
+    set!("openTypeOS2TypoAscender", open_type_os2_typo_ascender);
+    set!("openTypeOS2TypoDescender", open_type_os2_typo_descender);
+    set!("openTypeOS2TypoLineGap", open_type_os2_typo_line_gap);
+    set!("openTypeOS2WinAscent", open_type_os2_win_ascent);
+    set!("openTypeOS2WinDescent", open_type_os2_win_descent);
+    set!("openTypeOS2Type", open_type_os2_type);
+    set!("openTypeOS2SubscriptXSize", open_type_os2_subscript_x_size);
+    set!("openTypeOS2SubscriptYSize", open_type_os2_subscript_y_size);
+    set!(
+        "openTypeOS2SubscriptXOffset",
+        open_type_os2_subscript_x_offset
+    );
+    set!(
+        "openTypeOS2SubscriptYOffset",
+        open_type_os2_subscript_y_offset
+    );
+    set!(
+        "openTypeOS2SuperscriptXSize",
+        open_type_os2_superscript_x_size
+    );
+    set!(
+        "openTypeOS2SuperscriptYSize",
+        open_type_os2_superscript_y_size
+    );
+    set!(
+        "openTypeOS2SuperscriptXOffset",
+        open_type_os2_superscript_x_offset
+    );
+    set!(
+        "openTypeOS2SuperscriptYOffset",
+        open_type_os2_superscript_y_offset
+    );
+    set!("openTypeOS2StrikeoutSize", open_type_os2_strikeout_size);
+    set!(
+        "openTypeOS2StrikeoutPosition",
+        open_type_os2_strikeout_position
+    );
+
+    // OpenType vhea Table Fields.
+    set!(
+        "openTypeVheaVertTypoAscender",
+        open_type_vhea_vert_typo_ascender
+    );
+    set!(
+        "openTypeVheaVertTypoDescender",
+        open_type_vhea_vert_typo_descender
+    );
+    set!(
+        "openTypeVheaVertTypoLineGap",
+        open_type_vhea_vert_typo_line_gap
+    );
+    set!("openTypeVheaCaretSlopeRise", open_type_vhea_caret_slope_rise);
+    set!("openTypeVheaCaretSlopeRun", open_type_vhea_caret_slope_run);
+    set!("openTypeVheaCaretOffset", open_type_vhea_caret_offset);
+
+    // PostScript Specific Data.
+    set!("postscriptFontName", postscript_font_name);
+    set!("postscriptFullName", postscript_full_name);
+    set!("postscriptSlantAngle", postscript_slant_angle);
+    set!("postscriptUniqueID", postscript_unique_id);
+    set!(
+        "postscriptUnderlineThickness",
+        postscript_underline_thickness
+    );
+    set!("postscriptUnderlinePosition", postscript_underline_position);
+    set!("postscriptIsFixedPitch", postscript_is_fixed_pitch);
+    macro_rules! set_number_list {
+        ($key:literal, $field:ident) => {
+            if let Some(values) = &info.$field {
+                let values: Vec<f64> = values.iter().map(|v| v.get()).collect();
+                dict.insert($key, values.to_object(py));
+            }
+        };
+    }
+    set_number_list!("postscriptBlueValues", postscript_blue_values);
+    set_number_list!("postscriptOtherBlues", postscript_other_blues);
+    set_number_list!("postscriptFamilyBlues", postscript_family_blues);
+    set_number_list!("postscriptFamilyOtherBlues", postscript_family_other_blues);
+    set_number_list!("postscriptStemSnapH", postscript_stem_snap_h);
+    set_number_list!("postscriptStemSnapV", postscript_stem_snap_v);
+    set!("postscriptBlueFuzz", postscript_blue_fuzz);
+    set!("postscriptBlueShift", postscript_blue_shift);
+    set!("postscriptBlueScale", postscript_blue_scale);
+    set!("postscriptForceBold", postscript_force_bold);
+    set!("postscriptDefaultWidthX", postscript_default_width_x);
+    set!("postscriptNominalWidthX", postscript_nominal_width_x);
+    set!("postscriptWeightName", postscript_weight_name);
+    set!("postscriptDefaultCharacter", postscript_default_character);
+    if let Some(character_set) = &info.postscript_windows_character_set {
+        dict.insert(
+            "postscriptWindowsCharacterSet",
+            (*character_set as u8).to_object(py),
+        );
+    }
+
+    // Macintosh FOND Resource Data.
+    set!("macintoshFONDName", macintosh_fond_name);
+    set!("macintoshFONDFamilyID", macintosh_fond_family_id);
+
+    dict.into_py_dict(py).to_object(py)
+}
+
 fn convert_object_lib(olib: &plist::Dictionary, py: Python) -> PyResult<PyObject> {
     let mut object_lib = HashMap::<&str, PyObject>::new();
     for (key, value) in olib.iter() {
@@ -339,10 +1330,30 @@ fn convert_lib_key_value(key: &str, value: &plist::Value, py: Python) -> PyResul
             py_d.to_object(py)
         }
         plist::Value::Boolean(b) => b.to_object(py),
-        plist::Value::Data(d) => d.to_object(py),
-        // plist::Value::Date(d) => {
-        //     let date: std::time::SystemTime = d.into();
-        // }
+        // plist data is arbitrary binary data, so it maps to `bytes` rather
+        // than the list of ints a generic `Vec<u8>` conversion would produce.
+        plist::Value::Data(d) => pyo3::types::PyBytes::new(py, d).to_object(py),
+        // plist dates are always UTC, so they round-trip to a timezone-aware
+        // `datetime.datetime` rather than a naive one.
+        plist::Value::Date(d) => {
+            let since_epoch = std::time::SystemTime::from(*d)
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_err(|e| {
+                    PyException::new_err(format!(
+                        "lib element contains an invalid date for key '{}': {}",
+                        key, e
+                    ))
+                })?;
+            let utc = PyModule::import(py, "datetime")?
+                .getattr("timezone")?
+                .getattr("utc")?;
+            pyo3::types::PyDateTime::from_timestamp(
+                py,
+                since_epoch.as_secs_f64(),
+                Some(utc.downcast()?),
+            )?
+            .to_object(py)
+        }
         plist::Value::Real(r) => r.to_object(py),
         plist::Value::Integer(i) => {
             if let Some(i) = i.as_signed() {
@@ -403,3 +1414,212 @@ fn dump_object_libs(glyph: &norad::Glyph) -> norad::Plist {
 
     object_libs
 }
+
+const ILLEGAL_CHARACTERS: &[char] = &[
+    '"', '*', '+', '/', ':', '<', '>', '?', '[', '\\', ']', '|',
+];
+const RESERVED_FILE_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "CLOCK$", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7",
+    "COM8", "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+const MAX_FILE_NAME_LENGTH: usize = 255;
+
+/// Compute the `.glif` file name for `glyph_name`, following the UFO glyph
+/// naming algorithm (as implemented by ufoLib/defcon's `userNameToFileName`):
+/// illegal and control characters are replaced, reserved Windows names such as
+/// `con` get a leading underscore, an underscore is inserted
+/// after every uppercase letter so case-insensitive file systems can't
+/// collide two different glyph names, the result is clamped to
+/// `MAX_FILE_NAME_LENGTH` bytes, and a zero-padded counter is appended if the
+/// name still collides with one already in `existing`.
+#[pyfunction(
+    existing = "std::collections::HashSet::new()",
+    prefix = "\"\"",
+    suffix = "\".glif\""
+)]
+#[pyo3(text_signature = "(glyph_name, /, existing=set(), prefix='', suffix='.glif')")]
+fn user_name_to_file_name(
+    glyph_name: String,
+    existing: std::collections::HashSet<String>,
+    prefix: &str,
+    suffix: &str,
+) -> String {
+    let existing: std::collections::HashSet<String> =
+        existing.iter().map(|name| name.to_lowercase()).collect();
+
+    let mut name: String = glyph_name
+        .chars()
+        .map(|c| if ILLEGAL_CHARACTERS.contains(&c) || c.is_ascii_control() { '_' } else { c })
+        .collect();
+
+    if name.starts_with('.') {
+        name = format!("_{}", &name[1..]);
+    }
+
+    name = name
+        .split('.')
+        .map(|part| {
+            // A part with an uppercase letter gets underscores inserted below
+            // and so can no longer spell a reserved name.
+            if RESERVED_FILE_NAMES.contains(&part.to_uppercase().as_str())
+                && !part.chars().any(|c| c.is_uppercase())
+            {
+                format!("_{}", part)
+            } else {
+                part.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".");
+
+    if name.chars().any(|c| c.is_uppercase()) {
+        name = name
+            .chars()
+            .flat_map(|c| {
+                if c.is_uppercase() {
+                    vec![c, '_']
+                } else {
+                    vec![c]
+                }
+            })
+            .collect();
+    }
+
+    let overage = (prefix.len() + name.len() + suffix.len()).saturating_sub(MAX_FILE_NAME_LENGTH);
+    if overage > 0 {
+        let mut keep = name.len().saturating_sub(overage);
+        while keep > 0 && !name.is_char_boundary(keep) {
+            keep -= 1;
+        }
+        name.truncate(keep);
+    }
+
+    let mut file_name = format!("{}{}{}", prefix, name, suffix);
+    if existing.contains(&file_name.to_lowercase()) {
+        // Make room for the 15-digit counter within the length limit.
+        let overage = (prefix.len() + name.len() + 15 + suffix.len())
+            .saturating_sub(MAX_FILE_NAME_LENGTH);
+        let mut keep = name.len().saturating_sub(overage);
+        while keep > 0 && !name.is_char_boundary(keep) {
+            keep -= 1;
+        }
+        name.truncate(keep);
+        let mut counter = 1u64;
+        loop {
+            let candidate = format!("{}{}{:015}{}", prefix, name, counter, suffix);
+            if !existing.contains(&candidate.to_lowercase()) {
+                file_name = candidate;
+                break;
+            }
+            counter += 1;
+        }
+    }
+
+    file_name
+}
+
+#[cfg(test)]
+mod user_name_to_file_name_tests {
+    use super::{user_name_to_file_name, MAX_FILE_NAME_LENGTH};
+    use std::collections::HashSet;
+
+    fn convert(name: &str) -> String {
+        user_name_to_file_name(name.to_string(), HashSet::new(), "", ".glif")
+    }
+
+    #[test]
+    fn passes_through_plain_names() {
+        assert_eq!(convert("abc"), "abc.glif");
+    }
+
+    #[test]
+    fn inserts_underscore_after_uppercase_letters() {
+        // Regression test: the underscore must come *after* the uppercase
+        // letter, not before, so "Aacute" and "aacute" don't collide on a
+        // case-insensitive file system while still sorting near each other.
+        assert_eq!(convert("Aacute"), "A_acute.glif");
+    }
+
+    #[test]
+    fn escapes_leading_period() {
+        assert_eq!(convert(".notdef"), "_notdef.glif");
+    }
+
+    #[test]
+    fn escapes_windows_reserved_stems() {
+        assert_eq!(convert("con"), "_con.glif");
+    }
+
+    #[test]
+    fn clamps_to_max_file_name_length() {
+        let long_name = "a".repeat(300);
+        let result = convert(&long_name);
+        assert_eq!(result, format!("{}.glif", "a".repeat(250)));
+        assert_eq!(result.len(), MAX_FILE_NAME_LENGTH);
+    }
+
+    #[test]
+    fn truncates_multi_byte_names_on_a_char_boundary() {
+        // "€" is a 3-byte UTF-8 character; naively truncating by byte offset
+        // can land inside a codepoint and panic. This must not panic, and
+        // the result must stay within the max length and be valid UTF-8.
+        let long_name = "\u{20ac}".repeat(100);
+        let result = convert(&long_name);
+        assert!(result.len() <= MAX_FILE_NAME_LENGTH);
+        assert!(result.ends_with(".glif"));
+    }
+
+    #[test]
+    fn disambiguates_clashes_with_a_zero_padded_counter() {
+        let mut existing = HashSet::new();
+        existing.insert("test.glif".to_string());
+        let result = user_name_to_file_name("test".to_string(), existing, "", ".glif");
+        assert_eq!(result, "test000000000000001.glif");
+    }
+}
+
+#[cfg(test)]
+mod glyph_round_trip_tests {
+    use super::*;
+
+    #[test]
+    fn write_glyph_then_read_glyph_round_trips() {
+        Python::with_gil(|py| {
+            let mut point_a: HashMap<String, PyObject> = HashMap::new();
+            point_a.insert("x".to_string(), 0.0_f64.to_object(py));
+            point_a.insert("y".to_string(), 0.0_f64.to_object(py));
+            point_a.insert("type".to_string(), "move".to_object(py));
+
+            let mut point_b: HashMap<String, PyObject> = HashMap::new();
+            point_b.insert("x".to_string(), 100.0_f64.to_object(py));
+            point_b.insert("y".to_string(), 0.0_f64.to_object(py));
+            point_b.insert("type".to_string(), "line".to_object(py));
+
+            let mut contour: HashMap<String, PyObject> = HashMap::new();
+            contour.insert("points".to_string(), vec![point_a, point_b].to_object(py));
+
+            let mut glyph_dict: HashMap<String, PyObject> = HashMap::new();
+            glyph_dict.insert("name".to_string(), "A".to_object(py));
+            glyph_dict.insert("width".to_string(), 500.0_f64.to_object(py));
+            glyph_dict.insert("contours".to_string(), vec![contour].to_object(py));
+
+            let glif_path = std::env::temp_dir().join("readwrite_ufo_glif_round_trip_test_A.glif");
+            let glif_path = glif_path.to_str().unwrap();
+
+            write_glyph(glif_path, glyph_dict, '"', "  ", py).unwrap();
+            let round_tripped = read_glyph(glif_path).unwrap();
+            let _ = std::fs::remove_file(glif_path);
+
+            let round_tripped: HashMap<String, PyObject> = round_tripped.extract(py).unwrap();
+            let width: f64 = round_tripped["width"].extract(py).unwrap();
+            assert_eq!(width, 500.0);
+
+            let contours: Vec<HashMap<String, PyObject>> =
+                round_tripped["contours"].extract(py).unwrap();
+            assert_eq!(contours.len(), 1);
+            let points: Vec<HashMap<String, PyObject>> =
+                contours[0]["points"].extract(py).unwrap();
+            assert_eq!(points.len(), 2);
+        });
+    }
+}